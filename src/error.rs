@@ -24,7 +24,8 @@ pub enum RequestError {
     /// implement the XML-RPC spec.
     ParseError(ParseError),
 
-    // TODO make this extensible. anything missing?
+    /// The server returned a `<fault>` response instead of a valid result.
+    Fault(Fault),
 }
 
 impl From<HyperError> for RequestError {
@@ -45,11 +46,18 @@ impl From<io::Error> for RequestError {
     }
 }
 
+impl From<Fault> for RequestError {
+    fn from(e: Fault) -> Self {
+        RequestError::Fault(e)
+    }
+}
+
 impl Display for RequestError {
     fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
         match *self {
             RequestError::HyperError(ref err) => write!(fmt, "HTTP error: {}", err),
             RequestError::ParseError(ref err) => write!(fmt, "parse error: {}", err),
+            RequestError::Fault(ref err) => write!(fmt, "{}", err),
         }
     }
 }
@@ -59,6 +67,15 @@ impl Error for RequestError {
         match *self {
             RequestError::HyperError(ref err) => err.description(),
             RequestError::ParseError(ref err) => err.description(),
+            RequestError::Fault(ref err) => err.description(),
+        }
+    }
+
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            RequestError::HyperError(ref err) => Some(err),
+            RequestError::ParseError(ref err) => Some(err),
+            RequestError::Fault(ref err) => Some(err),
         }
     }
 }
@@ -80,7 +97,33 @@ pub enum ParseError {
         expected: String,
         /// The position of the unexpected data inside the XML document.
         position: TextPosition,
-    }
+    },
+
+    /// The document ended before the expected element, attribute, etc. was found.
+    UnexpectedEof {
+        /// A short description of the kind of data that was expected.
+        expected: String,
+        /// The position at which the document ended.
+        position: TextPosition,
+    },
+
+    /// A closing tag didn't match the currently open tag.
+    EndTagMismatch {
+        /// The name of the currently open tag.
+        expected: String,
+        /// The name found in the closing tag.
+        found: String,
+        /// The position of the mismatched end tag.
+        position: TextPosition,
+    },
+
+    /// A `<struct>` contained two `<member>`s with the same `<name>`.
+    DuplicateStructMember {
+        /// The repeated member name.
+        name: String,
+        /// The position of the second, duplicate `<member>`.
+        position: TextPosition,
+    },
 }
 
 impl From<XmlError> for ParseError {
@@ -106,16 +149,129 @@ impl Display for ParseError {
             } => {
                 write!(fmt, "expected {} at {}", expected, position)
             }
+            ParseError::UnexpectedEof {
+                ref expected,
+                ref position,
+            } => {
+                write!(fmt, "unexpected end of document, expected {} at {}", expected, position)
+            }
+            ParseError::EndTagMismatch {
+                ref expected,
+                ref found,
+                ref position,
+            } => {
+                write!(fmt, "mismatched end tag: expected </{}>, found </{}> at {}", expected, found, position)
+            }
+            ParseError::DuplicateStructMember {
+                ref name,
+                ref position,
+            } => {
+                write!(fmt, "duplicate struct member `{}` at {}", name, position)
+            }
         }
     }
 }
 
+impl ParseError {
+    /// Renders this error as a diagnostic, pointing at the exact spot in `source` that caused it.
+    ///
+    /// For the variants that carry a `TextPosition` (`UnexpectedXml`, `UnexpectedEof`,
+    /// `EndTagMismatch`, `DuplicateStructMember`), this prints the offending line followed by a
+    /// caret (`^`) under the column at which the error was found, expanding tabs so the caret
+    /// lines up. The column is clamped to the line's length. Falls back to the plain `Display`
+    /// message for other variants, or if `position` doesn't actually fall inside `source`.
+    pub fn diagnostic(&self, source: &str) -> String {
+        let position = match *self {
+            ParseError::UnexpectedXml { ref position, .. } => position,
+            ParseError::UnexpectedEof { ref position, .. } => position,
+            ParseError::EndTagMismatch { ref position, .. } => position,
+            ParseError::DuplicateStructMember { ref position, .. } => position,
+            _ => return self.to_string(),
+        };
+
+        let line = match source.lines().nth(position.row as usize) {
+            Some(line) => line,
+            None => return self.to_string(),
+        };
+
+        const TAB_WIDTH: usize = 4;
+        let mut rendered = String::new();
+        let mut caret_column = rendered.len();
+        for (i, ch) in line.chars().enumerate() {
+            if i == position.column as usize {
+                caret_column = rendered.chars().count();
+            }
+            if ch == '\t' {
+                rendered.push_str(&" ".repeat(TAB_WIDTH));
+            } else {
+                rendered.push(ch);
+            }
+        }
+        if position.column as usize >= line.chars().count() {
+            caret_column = rendered.chars().count();
+        }
+
+        format!("{}\n{}\n{}^", self, rendered, " ".repeat(caret_column))
+    }
+}
+
 impl Error for ParseError {
     fn description(&self) -> &str {
         match *self {
             ParseError::XmlError(ref err) => err.description(),
             ParseError::InvalidValue(ref desc) => desc,
             ParseError::UnexpectedXml { .. } => "unexpected XML content",
+            ParseError::UnexpectedEof { .. } => "unexpected end of document",
+            ParseError::EndTagMismatch { .. } => "mismatched end tag",
+            ParseError::DuplicateStructMember { .. } => "duplicate struct member",
+        }
+    }
+
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            ParseError::XmlError(ref err) => Some(err),
+            ParseError::InvalidValue(..) => None,
+            ParseError::UnexpectedXml { .. } => None,
+            ParseError::UnexpectedEof { .. } => None,
+            ParseError::EndTagMismatch { .. } => None,
+            ParseError::DuplicateStructMember { .. } => None,
+        }
+    }
+}
+
+/// An error that can occur when converting a `Value` into a concrete Rust type.
+#[derive(Debug, PartialEq)]
+pub enum ValueError {
+    /// The `Value` was not of the expected variant.
+    WrongType {
+        /// The name of the type that was expected.
+        expected: &'static str,
+        /// The name of the variant that was actually found.
+        found: &'static str,
+    },
+
+    /// A `Value::Struct` was missing a required field.
+    MissingField(String),
+}
+
+impl Display for ValueError {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        match *self {
+            ValueError::WrongType { expected, found } => {
+                write!(fmt, "expected {}, found {}", expected, found)
+            }
+            ValueError::MissingField(ref name) => {
+                write!(fmt, "missing field `{}`", name)
+            }
+        }
+    }
+}
+
+impl Error for ValueError {
+    fn description(&self) -> &str {
+        match *self {
+            ValueError::WrongType { .. } => "value had an unexpected type",
+            ValueError::MissingField(..) => "struct was missing a required field",
         }
     }
 }
@@ -153,3 +309,88 @@ impl Fault {
         }
     }
 }
+
+impl Display for Fault {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        write!(fmt, "faultCode {}: {}", self.fault_code, self.fault_string)
+    }
+}
+
+impl Error for Fault {
+    fn description(&self) -> &str {
+        &self.fault_string
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use xml::common::TextPosition;
+
+    #[test]
+    fn diagnostic_points_at_the_offending_column() {
+        let source = "<value>\n<int>AAA</int>\n</value>";
+        let err = ParseError::UnexpectedXml {
+            expected: "a number".to_string(),
+            position: TextPosition { row: 1, column: 5 },
+        };
+        assert_eq!(
+            err.diagnostic(source),
+            format!("{}\n<int>AAA</int>\n     ^", err)
+        );
+    }
+
+    #[test]
+    fn diagnostic_falls_back_when_out_of_range() {
+        let err = ParseError::UnexpectedXml {
+            expected: "a number".to_string(),
+            position: TextPosition { row: 99, column: 0 },
+        };
+        assert_eq!(err.diagnostic("<value></value>"), err.to_string());
+    }
+
+    #[test]
+    fn end_tag_mismatch_display() {
+        let position = TextPosition { row: 0, column: 0 };
+        let err = ParseError::EndTagMismatch {
+            expected: "struct".to_string(),
+            found: "array".to_string(),
+            position: position,
+        };
+        assert_eq!(
+            err.to_string(),
+            format!("mismatched end tag: expected </struct>, found </array> at {}", position)
+        );
+    }
+
+    #[test]
+    fn duplicate_struct_member_display() {
+        let position = TextPosition { row: 2, column: 4 };
+        let err = ParseError::DuplicateStructMember {
+            name: "count".to_string(),
+            position: position,
+        };
+        assert_eq!(err.to_string(), format!("duplicate struct member `count` at {}", position));
+    }
+
+    #[test]
+    fn parse_error_source_distinguishes_wrapping_from_leaf_variants() {
+        let wrapping = ParseError::from(io::Error::from(io::ErrorKind::Other));
+        assert!(wrapping.source().is_some());
+
+        let leaf = ParseError::InvalidValue("AAA".to_string());
+        assert!(leaf.source().is_none());
+    }
+
+    #[test]
+    fn request_error_source_wraps_inner_errors() {
+        let hyper_err = RequestError::from(io::Error::from(io::ErrorKind::Other));
+        assert!(hyper_err.source().is_some());
+    }
+
+    #[test]
+    fn request_error_source_wraps_fault() {
+        let fault_err = RequestError::Fault(Fault { fault_code: 1, fault_string: "oops".to_string() });
+        assert!(fault_err.source().is_some());
+    }
+}
@@ -1,11 +1,13 @@
 //! Contains the different types of values understood by XML-RPC.
 
+use error::ValueError;
 use utils::{escape_xml, format_datetime};
 
 use base64::encode;
 use iso8601::DateTime;
 
 use std::collections::BTreeMap;
+use std::convert::TryFrom;
 use std::io::{self, Write};
 
 /// The possible XML-RPC values.
@@ -34,6 +36,68 @@ pub enum Value {
 }
 
 impl Value {
+    /// Returns the name of this value's variant, for use in error messages.
+    fn type_name(&self) -> &'static str {
+        match *self {
+            Value::Int(..) => "Int",
+            Value::Bool(..) => "Bool",
+            Value::String(..) => "String",
+            Value::Double(..) => "Double",
+            Value::DateTime(..) => "DateTime",
+            Value::Base64(..) => "Base64",
+            Value::Struct(..) => "Struct",
+            Value::Array(..) => "Array",
+        }
+    }
+
+    /// Returns the contained integer, if this is a `Value::Int`.
+    pub fn as_i32(&self) -> Result<i32, ValueError> {
+        match *self {
+            Value::Int(i) => Ok(i),
+            ref other => Err(ValueError::WrongType { expected: "Int", found: other.type_name() }),
+        }
+    }
+
+    /// Returns the contained boolean, if this is a `Value::Bool`.
+    pub fn as_bool(&self) -> Result<bool, ValueError> {
+        match *self {
+            Value::Bool(b) => Ok(b),
+            ref other => Err(ValueError::WrongType { expected: "Bool", found: other.type_name() }),
+        }
+    }
+
+    /// Borrows the contained string, if this is a `Value::String`.
+    pub fn as_str(&self) -> Result<&str, ValueError> {
+        match *self {
+            Value::String(ref s) => Ok(s),
+            ref other => Err(ValueError::WrongType { expected: "String", found: other.type_name() }),
+        }
+    }
+
+    /// Borrows the contained array, if this is a `Value::Array`.
+    pub fn as_array(&self) -> Result<&[Value], ValueError> {
+        match *self {
+            Value::Array(ref array) => Ok(array),
+            ref other => Err(ValueError::WrongType { expected: "Array", found: other.type_name() }),
+        }
+    }
+
+    /// Borrows the contained struct, if this is a `Value::Struct`.
+    pub fn as_struct(&self) -> Result<&BTreeMap<String, Value>, ValueError> {
+        match *self {
+            Value::Struct(ref map) => Ok(map),
+            ref other => Err(ValueError::WrongType { expected: "Struct", found: other.type_name() }),
+        }
+    }
+
+    /// Looks up a field by name, if this is a `Value::Struct` containing it.
+    pub fn get(&self, name: &str) -> Result<&Value, ValueError> {
+        match self.as_struct()?.get(name) {
+            Some(value) => Ok(value),
+            None => Err(ValueError::MissingField(name.to_string())),
+        }
+    }
+
     pub fn format<W: Write>(&self, fmt: &mut W) -> io::Result<()> {
         try!(writeln!(fmt, "<value>"));
 
@@ -124,9 +188,133 @@ impl From<Vec<u8>> for Value {
     }
 }
 
+impl TryFrom<Value> for i32 {
+    type Error = ValueError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Int(i) => Ok(i),
+            other => Err(ValueError::WrongType { expected: "Int", found: other.type_name() }),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a Value> for i32 {
+    type Error = ValueError;
+
+    fn try_from(value: &'a Value) -> Result<Self, Self::Error> {
+        value.as_i32()
+    }
+}
+
+impl TryFrom<Value> for bool {
+    type Error = ValueError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Bool(b) => Ok(b),
+            other => Err(ValueError::WrongType { expected: "Bool", found: other.type_name() }),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a Value> for bool {
+    type Error = ValueError;
+
+    fn try_from(value: &'a Value) -> Result<Self, Self::Error> {
+        value.as_bool()
+    }
+}
+
+impl TryFrom<Value> for String {
+    type Error = ValueError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::String(s) => Ok(s),
+            other => Err(ValueError::WrongType { expected: "String", found: other.type_name() }),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a Value> for String {
+    type Error = ValueError;
+
+    fn try_from(value: &'a Value) -> Result<Self, Self::Error> {
+        value.as_str().map(|s| s.to_string())
+    }
+}
+
+impl TryFrom<Value> for f64 {
+    type Error = ValueError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Double(d) => Ok(d),
+            other => Err(ValueError::WrongType { expected: "Double", found: other.type_name() }),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a Value> for f64 {
+    type Error = ValueError;
+
+    fn try_from(value: &'a Value) -> Result<Self, Self::Error> {
+        match *value {
+            Value::Double(d) => Ok(d),
+            ref other => Err(ValueError::WrongType { expected: "Double", found: other.type_name() }),
+        }
+    }
+}
+
+impl TryFrom<Value> for DateTime {
+    type Error = ValueError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::DateTime(dt) => Ok(dt),
+            other => Err(ValueError::WrongType { expected: "DateTime", found: other.type_name() }),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a Value> for DateTime {
+    type Error = ValueError;
+
+    fn try_from(value: &'a Value) -> Result<Self, Self::Error> {
+        match *value {
+            Value::DateTime(dt) => Ok(dt),
+            ref other => Err(ValueError::WrongType { expected: "DateTime", found: other.type_name() }),
+        }
+    }
+}
+
+impl TryFrom<Value> for Vec<u8> {
+    type Error = ValueError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Base64(data) => Ok(data),
+            other => Err(ValueError::WrongType { expected: "Base64", found: other.type_name() }),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a Value> for Vec<u8> {
+    type Error = ValueError;
+
+    fn try_from(value: &'a Value) -> Result<Self, Self::Error> {
+        match *value {
+            Value::Base64(ref data) => Ok(data.clone()),
+            ref other => Err(ValueError::WrongType { expected: "Base64", found: other.type_name() }),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use error::ValueError;
     use std::str;
     use std::collections::BTreeMap;
 
@@ -147,4 +335,33 @@ mod tests {
         Value::Struct(map).format(&mut output).unwrap();
         assert_eq!(str::from_utf8(&output).unwrap(), "<value>\n<struct>\n<member>\n<name>x&amp;&lt;x</name>\n<value>\n<boolean>1</boolean>\n</value>\n</member>\n</struct>\n</value>\n");
     }
+
+    #[test]
+    fn try_from_converts_matching_variant() {
+        let value = Value::Int(42);
+        let n = i32::try_from(&value).unwrap();
+        assert_eq!(n, 42);
+    }
+
+    #[test]
+    fn try_from_rejects_mismatched_variant() {
+        let value = Value::Int(42);
+        assert_eq!(
+            bool::try_from(value).unwrap_err(),
+            ValueError::WrongType { expected: "Bool", found: "Int" }
+        );
+    }
+
+    #[test]
+    fn get_looks_up_struct_field() {
+        let mut map: BTreeMap<String, Value> = BTreeMap::new();
+        map.insert("count".to_string(), Value::Int(7));
+        let value = Value::Struct(map);
+
+        assert_eq!(value.get("count").unwrap().as_i32().unwrap(), 7);
+        assert_eq!(
+            value.get("missing").unwrap_err(),
+            ValueError::MissingField("missing".to_string())
+        );
+    }
 }